@@ -0,0 +1,94 @@
+use crate::dedup::KeepPolicy;
+use crate::raw_extensions::RAW_IMAGE_EXTENSIONS;
+use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
+
+const JPG_FOLDER: &str = "JPG";
+const JPG_ALLOWED_FILE_EXTENSIONS: &[&str] = &["jpg", "jpeg"];
+
+/// Which side of the RAW/JPG pair gets culled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Direction {
+    /// Keep RAW files, delete JPGs that have no surviving RAW sibling.
+    JpgToRaw,
+    /// Keep JPG files, delete RAW files that have no surviving JPG sibling.
+    RawToJpg,
+}
+
+/// Deletes JPGs (or RAWs) left behind after culling their sibling in the
+/// other format.
+#[derive(Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Directory containing the RAW files. Defaults to the directory containing this executable.
+    #[arg(long)]
+    pub raw_dir: Option<PathBuf>,
+
+    /// Directory containing the JPG files. Defaults to `<raw-dir>/JPG`.
+    #[arg(long)]
+    pub jpg_dir: Option<PathBuf>,
+
+    /// RAW file extensions to treat as RAW files.
+    #[arg(long, value_delimiter = ',', default_values = RAW_IMAGE_EXTENSIONS)]
+    pub raw_ext: Vec<String>,
+
+    /// JPG file extensions to treat as JPG files.
+    #[arg(long, value_delimiter = ',', default_values = JPG_ALLOWED_FILE_EXTENSIONS)]
+    pub jpg_ext: Vec<String>,
+
+    /// Which side of the pair gets culled.
+    #[arg(long, value_enum, default_value_t = Direction::JpgToRaw)]
+    pub direction: Direction,
+
+    /// List the files that would be deleted, without deleting them.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Write a report of matched/unmatched files to this path (`.json` for JSON, anything else for text).
+    #[arg(long)]
+    pub report: Option<PathBuf>,
+
+    /// Force a specific deletion backend instead of the OS default.
+    #[arg(long, value_enum)]
+    pub delete_mode: Option<DeleteMode>,
+
+    /// Only scan files whose path (relative to their root) matches one of these globs.
+    #[arg(long)]
+    pub include: Vec<String>,
+
+    /// Skip files whose path (relative to their root) matches one of these globs.
+    #[arg(long)]
+    pub exclude: Vec<String>,
+
+    /// Number of worker threads to scan and delete with. Defaults to the number of CPUs.
+    #[arg(long, default_value_t = num_cpus::get())]
+    pub threads: usize,
+
+    /// Hash the surviving files to also find and cull exact duplicates
+    /// (identical content under different names). Matching by name is the
+    /// same with or without this flag; hashes are not compared across the
+    /// RAW/JPG pair.
+    #[arg(long)]
+    pub verify_hash: bool,
+
+    /// Which copy of an exact duplicate to keep. Only used with `--verify-hash`.
+    #[arg(long, value_enum, default_value_t = KeepPolicy::Newest)]
+    pub keep_policy: KeepPolicy,
+}
+
+/// Forces a specific `Deleter` backend instead of picking one from the OS.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum DeleteMode {
+    Finder,
+    Trash,
+    Permanent,
+}
+
+impl Cli {
+    /// Resolves `--jpg-dir`, falling back to `<raw-dir>/JPG`.
+    pub fn jpg_dir(&self, raw_dir: &std::path::Path) -> PathBuf {
+        self.jpg_dir
+            .clone()
+            .unwrap_or_else(|| raw_dir.join(JPG_FOLDER))
+    }
+}
@@ -0,0 +1,83 @@
+use crate::hash::hash_file;
+use crate::scan::Filename;
+use clap::ValueEnum;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Which copy to keep when several files hash identically.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum KeepPolicy {
+    Newest,
+    Oldest,
+}
+
+/// A set of files with identical content; `kept` is the one `KeepPolicy`
+/// chose to survive, `duplicates` are the rest.
+pub struct DuplicateGroup {
+    pub kept: PathBuf,
+    pub duplicates: Vec<Filename>,
+}
+
+fn modified_time(path: &Path) -> SystemTime {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+/// Groups `files` by content hash and flags every member of a group but
+/// the one `keep_policy` selects. Files that fail to hash (e.g. removed
+/// mid-scan, unreadable) are left out entirely rather than risk a wrong
+/// deletion.
+pub fn find_exact_duplicates(files: Vec<Filename>, keep_policy: KeepPolicy) -> Vec<DuplicateGroup> {
+    let mut by_hash: HashMap<u64, Vec<Filename>> = HashMap::new();
+
+    for file in files {
+        if let Ok(hash) = hash_file(&file.path) {
+            by_hash.entry(hash).or_default().push(file);
+        }
+    }
+
+    by_hash
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .map(|mut group| {
+            group.sort_by_key(|file| modified_time(&file.path));
+            let kept = match keep_policy {
+                KeepPolicy::Newest => group.pop().unwrap(),
+                KeepPolicy::Oldest => group.remove(0),
+            };
+            DuplicateGroup {
+                kept: kept.path,
+                duplicates: group,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::get_filenames_of_folder;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_find_exact_duplicates_keeps_one_copy() {
+        let tmp_dir = tempdir().unwrap();
+        let root = tmp_dir.path();
+
+        fs::write(root.join("a.jpg"), b"same content").unwrap();
+        fs::write(root.join("b.jpg"), b"same content").unwrap();
+        fs::write(root.join("c.jpg"), b"different content").unwrap();
+
+        let files = get_filenames_of_folder(root).unwrap();
+        let groups = find_exact_duplicates(files, KeepPolicy::Newest);
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].duplicates.len(), 1);
+
+        tmp_dir.close().unwrap();
+    }
+}
@@ -0,0 +1,105 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Converts a path to the colon-separated HFS form that AppleScript/Finder
+/// expects, e.g. `/Users/x/JPG` -> `Macintosh HD:Users:x:JPG`.
+fn convert_to_hfs_path(path: &Path) -> Result<String> {
+    let stdout = path
+        .to_str()
+        .with_context(|| {
+            format!("❌ Could not get string from path. {:?}", path)
+        })
+        .and_then(|path| {
+            Command::new("osascript")
+                .arg("-e")
+                .arg(format!(r#"POSIX file "{}" as alias as text"#, path))
+                .output()
+                .with_context(|| {
+                    format!("❌ Cannot get HFS convert Output {}", path)
+                })
+                .map(|op| op.stdout)
+        })?;
+
+    String::from_utf8(stdout)
+        .context("❌ Could not convert to utf8")
+        .map(|s| s.trim().to_string())
+}
+
+fn check_result(output: &Output, file: &str) -> Result<()> {
+    if output.status.success() {
+        Ok(())
+    } else {
+        let error = String::from_utf8_lossy(&output.stderr);
+        if error.contains("29:106") {
+            anyhow::bail!("❌ Could not delete file cause couldn't find the file {:?}", file);
+        } else {
+            anyhow::bail!("❌ {:?}", &error);
+        }
+    }
+}
+
+/// A backend capable of removing a single file. Implementations decide
+/// whether that means a Finder trash move, the platform recycle
+/// bin, or an irreversible `remove_file`. `Send + Sync` so a single
+/// instance can be shared across the rayon thread pool that drives the
+/// deletion loop.
+pub trait Deleter: Send + Sync {
+    fn delete(&self, path: &Path) -> Result<()>;
+}
+
+/// Deletes files through the macOS Finder via AppleScript, so they land in
+/// the Trash exactly as if the user had dragged them there. This is the
+/// original behavior of the tool and only works on macOS.
+pub struct FinderDeleter;
+
+impl Deleter for FinderDeleter {
+    fn delete(&self, path: &Path) -> Result<()> {
+        let folder = path.parent().with_context(|| {
+            format!("❌ Could not get parent directory of {:?}", path)
+        })?;
+        let filename = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .with_context(|| format!("❌ Could not get filename of {:?}", path))?;
+        let hfs_folder_path = convert_to_hfs_path(folder)?;
+
+        let output = Command::new("osascript")
+            .arg("-e")
+            .arg(format!(
+                r#"tell application "Finder" to delete (file "{}" of folder "{}")"#,
+                filename, hfs_folder_path
+            ))
+            .output()
+            .with_context(|| {
+                format!(
+                    "❌ Could not delete file {:?} of folder {:?}, cause the command failed",
+                    filename, hfs_folder_path
+                )
+            })?;
+
+        check_result(&output, filename)
+    }
+}
+
+/// Moves files to the platform Recycle Bin/Trash, so they can still be
+/// recovered. Works on Windows, macOS and Linux.
+pub struct TrashDeleter;
+
+impl Deleter for TrashDeleter {
+    fn delete(&self, path: &Path) -> Result<()> {
+        trash::delete(path)
+            .with_context(|| format!("❌ Could not move {:?} to trash", path))
+    }
+}
+
+/// Removes files from disk immediately, with no recycle bin safety net.
+pub struct PermanentDeleter;
+
+impl Deleter for PermanentDeleter {
+    fn delete(&self, path: &Path) -> Result<()> {
+        fs::remove_file(path)
+            .with_context(|| format!("❌ Could not permanently delete {:?}", path))
+    }
+}
@@ -0,0 +1,30 @@
+use anyhow::{Context, Result};
+use seahash::SeaHasher;
+use std::fs::File;
+use std::hash::Hasher;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Hashes a file's contents in buffered chunks so large RAWs don't get
+/// read into memory all at once. Used to group files whose contents are
+/// byte-for-byte identical.
+pub fn hash_file(path: &Path) -> Result<u64> {
+    let file = File::open(path).with_context(|| format!("❌ Could not open {:?}", path))?;
+    let mut reader = BufReader::new(file);
+    let mut hasher = SeaHasher::new();
+    let mut buffer = [0u8; CHUNK_SIZE];
+
+    loop {
+        let read = reader
+            .read(&mut buffer)
+            .with_context(|| format!("❌ Could not read {:?}", path))?;
+        if read == 0 {
+            break;
+        }
+        hasher.write(&buffer[..read]);
+    }
+
+    Ok(hasher.finish())
+}
@@ -1,48 +1,33 @@
+mod cli;
+mod dedup;
+mod deleter;
+mod hash;
+mod raw_extensions;
+mod report;
+mod scan;
+
 use anyhow::{Context, Result};
+use clap::Parser;
+use cli::{Cli, DeleteMode, Direction};
+use dedup::find_exact_duplicates;
+use deleter::{Deleter, FinderDeleter, PermanentDeleter, TrashDeleter};
+use rayon::prelude::*;
+use report::{Report, ReportEntry};
+use scan::{get_filenames_of_folder_with_valid_extension, Filename};
+use std::collections::HashSet;
 use std::env;
-use std::fs;
-use std::fs::DirEntry;
-use std::path::Path;
 use std::path::PathBuf;
-use std::process::{Command, Output};
-
-const JPG_FOLDER: &str = "JPG";
-const RAW_ALLOWED_FILE_EXTENSIONS: &[&str] = &["arw"];
-const JPG_ALLOWED_FILE_EXTENSIONS: &[&str] = &["jpg", "jpeg"];
-
-struct Filename {
-    filename: String,
-    name: String,
-    extension: String,
-}
 
-impl From<DirEntry> for Filename {
-    fn from(dir_entry: DirEntry) -> Self {
-        let path = dir_entry.path();
-        let filename = path
-            .file_name()
-            .map(|s| s.to_str().or(Some("")).unwrap())
-            .or(Some(""))
-            .unwrap()
-            .to_string();
-        let name = path
-            .file_stem()
-            .map(|s| s.to_str().or(Some("")).unwrap())
-            .or(Some(""))
-            .unwrap()
-            .to_string();
-        let extension = path
-            .extension()
-            .map(|s| s.to_str().or(Some("")).unwrap())
-            .or(Some(""))
-            .unwrap()
-            .to_string()
-            .to_lowercase();
-        Self {
-            filename,
-            name,
-            extension,
-        }
+/// Picks the deletion backend to use for the flagged files. Defaults to
+/// the platform's native behavior (Finder on macOS, Trash elsewhere) but
+/// can be overridden with `--delete-mode`.
+fn select_deleter(delete_mode: Option<DeleteMode>) -> Box<dyn Deleter> {
+    match delete_mode {
+        Some(DeleteMode::Finder) => Box::new(FinderDeleter),
+        Some(DeleteMode::Trash) => Box::new(TrashDeleter),
+        Some(DeleteMode::Permanent) => Box::new(PermanentDeleter),
+        None if cfg!(target_os = "macos") => Box::new(FinderDeleter),
+        None => Box::new(TrashDeleter),
     }
 }
 
@@ -55,131 +40,157 @@ fn get_pwd() -> Result<String> {
     Ok(path.to_string())
 }
 
-fn get_filenames_of_folder(path: PathBuf) -> Result<Vec<Filename>> {
-    fs::read_dir(path.clone())
-        .with_context(|| format!("❌ Could not read directory {:?}", path))
-        .map(|dir| {
-            dir.into_iter()
-                .filter_map(|file| match file {
-                    Ok(file) => Some(file.into()),
-                    _ => None,
-                })
-                .collect::<Vec<Filename>>()
-        })
-}
-
-fn get_filenames_of_folder_with_valid_extension(
-    path: PathBuf,
-    allowed_extensions: Vec<&str>,
-) -> Result<Vec<Filename>> {
-    get_filenames_of_folder(path).map(|files| {
-        files
-            .into_iter()
-            .filter(|file| {
-                allowed_extensions.contains(&file.extension.as_str())
-            })
-            .collect::<Vec<Filename>>()
-    })
-}
-
-fn find_duplicate_file(
+/// Splits `target_files` into those with a sibling in `compare_files` (same
+/// name, same shoot directory) and those without. The latter are the
+/// orphans `main` goes on to flag for deletion; the former are recorded in
+/// the report so it can show what was kept as well as what was culled.
+fn partition_matched_files(
     compare_files: Vec<Filename>,
     target_files: Vec<Filename>,
-) -> Vec<Filename> {
-    let compare_names = compare_files
+) -> (Vec<Filename>, Vec<Filename>) {
+    let compare_keys = compare_files
         .into_iter()
-        .map(|filename| filename.name)
-        .collect::<Vec<String>>();
+        .map(|filename| (filename.relative_dir(), filename.name))
+        .collect::<Vec<(PathBuf, String)>>();
 
-    target_files
-        .into_iter()
-        .filter(|filename| !compare_names.contains(&filename.name))
-        .collect::<Vec<Filename>>()
-}
-
-fn convert_to_hfs_path(path: PathBuf) -> Result<String> {
-    let stdout = path
-        .to_str()
-        .with_context(|| {
-            format!("❌ Could not get string from path. {:?}", path)
-        })
-        .and_then(|path| {
-            Command::new("osascript")
-                .arg("-e")
-                .arg(format!(r#"POSIX file "{}" as alias as text"#, path))
-                .output()
-                .with_context(|| {
-                    format!("❌ Cannot get HFS convert Output {}", path)
-                })
-                .map(|op| op.stdout)
-        })?;
-
-    String::from_utf8(stdout)
-        .context("❌ Could not convert to utf8")
-        .map(|s| s.trim().to_string())
-}
-
-fn print_result(output: &Output, file: &str) {
-    if output.status.success() {
-        println!("👍 Deleted {}", file);
-    } else {
-        let error = String::from_utf8_lossy(&output.stderr);
-        if error.contains("29:106") {
-            println!(
-                "❌ Could not delete file cause couldn't find the file {:?}",
-                file
-            );
-        } else {
-            println!("❌ {:?}", &error);
-        }
-    }
+    target_files.into_iter().partition(|filename| {
+        let key = (filename.relative_dir(), filename.name.clone());
+        compare_keys.contains(&key)
+    })
 }
 
-fn delete_files(filename: Filename, hfs_folder_path: String) -> Result<()> {
-    let filename = filename.filename;
-    Command::new("osascript")
-        .arg("-e")
-        .arg(format!(
-            r#"tell application "Finder" to delete (file "{}" of folder "{}")"#,
-            filename, hfs_folder_path
-        ))
-        .output()
-        .with_context(|| {
-            format!(
-                "❌ Could not delete file {:?} of folder {:?}, cause the command failed",
-                filename, hfs_folder_path
-            )
-        }).map(|output| {
-            print_result(&output, &filename);
-        })
+/// Sets up the global rayon pool used by scanning and deletion.
+fn set_number_of_threads(threads: usize) -> Result<()> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .context("❌ Could not set up the thread pool")
 }
 
 fn main() -> Result<()> {
     println!("🚀 Start deleting duplicated files");
 
-    let raw_folder_path = get_pwd()?;
-    let raw_folder_path = Path::new(&raw_folder_path).to_path_buf();
-    let jpg_folder_path = Path::new(&raw_folder_path).join(JPG_FOLDER);
-    let jpg_folder_path_hfs = convert_to_hfs_path(jpg_folder_path.clone())?;
+    let cli = Cli::parse();
+    set_number_of_threads(cli.threads)?;
+
+    let raw_folder_path = cli
+        .raw_dir
+        .clone()
+        .map(Ok)
+        .unwrap_or_else(|| get_pwd().map(PathBuf::from))?;
+    let jpg_folder_path = cli.jpg_dir(&raw_folder_path);
 
     let raw_files = get_filenames_of_folder_with_valid_extension(
-        raw_folder_path,
-        RAW_ALLOWED_FILE_EXTENSIONS.into(),
+        &raw_folder_path,
+        &cli.raw_ext,
+        &cli.include,
+        &cli.exclude,
     )?;
     let jpg_files = get_filenames_of_folder_with_valid_extension(
-        jpg_folder_path,
-        JPG_ALLOWED_FILE_EXTENSIONS.into(),
+        &jpg_folder_path,
+        &cli.jpg_ext,
+        &cli.include,
+        &cli.exclude,
     )?;
-    let unused_files_in_jpg_folder = find_duplicate_file(raw_files, jpg_files);
+    let raw_scanned = raw_files.len();
+    let jpg_scanned = jpg_files.len();
+
+    let (compare_files, target_files, orphan_reason, matched_reason) = match cli.direction {
+        Direction::JpgToRaw => (
+            raw_files,
+            jpg_files,
+            "no matching RAW file found",
+            "matching RAW file found",
+        ),
+        Direction::RawToJpg => (
+            jpg_files,
+            raw_files,
+            "no matching JPG file found",
+            "matching JPG file found",
+        ),
+    };
+
+    let (matched, unmatched) = partition_matched_files(compare_files, target_files);
+
+    let duplicate_groups = if cli.verify_hash {
+        find_exact_duplicates(matched.clone(), cli.keep_policy)
+    } else {
+        Vec::new()
+    };
 
-    unused_files_in_jpg_folder.into_iter().for_each(|file| {
-        let result = delete_files(file, jpg_folder_path_hfs.clone());
-        if result.is_err() {
-            println!("{:?}", result.err());
+    let mut flagged: Vec<(Filename, String)> = unmatched
+        .into_iter()
+        .map(|file| (file, orphan_reason.to_string()))
+        .collect();
+
+    let mut already_flagged = flagged
+        .iter()
+        .map(|(file, _)| file.path.clone())
+        .collect::<HashSet<PathBuf>>();
+
+    for group in duplicate_groups {
+        for duplicate in group.duplicates {
+            if already_flagged.insert(duplicate.path.clone()) {
+                flagged.push((
+                    duplicate,
+                    format!("exact duplicate of {:?}", group.kept),
+                ));
+            }
         }
-    });
+    }
+
+    if let Some(report_path) = &cli.report {
+        let report = Report {
+            raw_scanned,
+            jpg_scanned,
+            flagged: flagged.len(),
+            entries: flagged
+                .iter()
+                .map(|(file, reason)| (file, reason.as_str()))
+                .chain(
+                    matched
+                        .iter()
+                        .filter(|file| !already_flagged.contains(&file.path))
+                        .map(|file| (file, matched_reason)),
+                )
+                .map(|(file, reason)| ReportEntry {
+                    name: file.name.clone(),
+                    extension: file.extension.clone(),
+                    path: file.path.clone(),
+                    reason: reason.to_string(),
+                })
+                .collect(),
+        };
+        report.write_to_file(report_path)?;
+        println!("📝 Wrote report to {:?}", report_path);
+    }
+
+    if cli.dry_run {
+        println!("🔎 Dry run, the following files would be deleted:");
+        flagged
+            .iter()
+            .for_each(|(file, reason)| println!("  {:?} ({})", file.path, reason));
+
+        println!("✅ Done");
+        return Ok(());
+    }
+
+    let deleter = select_deleter(cli.delete_mode);
+    let results = flagged
+        .into_par_iter()
+        .map(|(file, _)| (file.filename, deleter.delete(&file.path)))
+        .collect::<Vec<(String, Result<()>)>>();
 
-    println!("✅ Done");
+    let failed = results.iter().filter(|(_, result)| result.is_err()).count();
+    let succeeded = results.len() - failed;
+
+    for (filename, result) in &results {
+        if let Err(err) = result {
+            println!("❌ Could not delete {}: {:?}", filename, err);
+        }
+    }
+
+    println!("✅ Done: {} deleted, {} failed", succeeded, failed);
 
     Ok(())
 }
@@ -187,13 +198,14 @@ fn main() -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use scan::get_filenames_of_folder;
     use std::fs;
     use std::fs::File;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use tempfile::tempdir;
 
     fn create_files(folder_path: PathBuf, number_of_files: usize, ext: &str) {
-        (0..number_of_files).into_iter().for_each(|i| {
+        (0..number_of_files).for_each(|i| {
             let file = folder_path.join(format!("test{}.{}", i, ext));
             File::create(file.clone()).unwrap();
         });
@@ -207,7 +219,7 @@ mod tests {
 
         create_files(raw_folder.into(), number_of_files, "arw");
 
-        let filenames = get_filenames_of_folder(raw_folder.into()).unwrap();
+        let filenames = get_filenames_of_folder(raw_folder).unwrap();
         assert_eq!(filenames.len(), number_of_files);
 
         tmp_dir.close().unwrap();
@@ -223,8 +235,10 @@ mod tests {
         create_files(raw_folder.into(), number_of_files, "jpg");
 
         let filenames = get_filenames_of_folder_with_valid_extension(
-            raw_folder.into(),
-            vec!["arw"],
+            raw_folder,
+            &["arw".to_string()],
+            &[],
+            &[],
         )
         .unwrap();
 
@@ -242,23 +256,63 @@ mod tests {
         let jpg_folder = raw_folder.join("JPG");
         fs::create_dir(jpg_folder.clone()).unwrap();
 
-        create_files(raw_folder.clone().into(), number_of_raw_files, "arw");
-        create_files(jpg_folder.clone().into(), number_of_jpg_files, "jpg");
+        create_files(raw_folder.into(), number_of_raw_files, "arw");
+        create_files(jpg_folder.clone(), number_of_jpg_files, "jpg");
 
         let raw_files = get_filenames_of_folder_with_valid_extension(
-            raw_folder.into(),
-            vec!["arw"],
+            raw_folder,
+            &["arw".to_string()],
+            &[],
+            &[],
         )
         .unwrap();
         let jpg_files = get_filenames_of_folder_with_valid_extension(
-            jpg_folder.into(),
-            vec!["jpg"],
+            &jpg_folder,
+            &["jpg".to_string()],
+            &[],
+            &[],
         )
         .unwrap();
 
-        let unused_files_in_jpg_folder = find_duplicate_file(raw_files, jpg_files);
+        let (matched, unused_files_in_jpg_folder) = partition_matched_files(raw_files, jpg_files);
 
+        assert_eq!(matched.len(), number_of_raw_files);
         assert_eq!(unused_files_in_jpg_folder.len(), number_of_jpg_files - number_of_raw_files);
         tmp_dir.close().unwrap();
     }
+
+    #[test]
+    fn test_partition_matched_files_scopes_by_directory() {
+        let tmp_dir = tempdir().unwrap();
+        let root = tmp_dir.path();
+        let shoot_a_raw = root.join("2024/wedding");
+        let shoot_a_jpg = root.join("2024/wedding/JPG");
+        let shoot_b_jpg = root.join("2024/portrait/JPG");
+        fs::create_dir_all(&shoot_a_raw).unwrap();
+        fs::create_dir_all(&shoot_a_jpg).unwrap();
+        fs::create_dir_all(&shoot_b_jpg).unwrap();
+
+        // Only shoot A has a surviving RAW for "test0".
+        create_files(shoot_a_raw, 1, "arw");
+        create_files(shoot_a_jpg, 1, "jpg");
+        create_files(shoot_b_jpg, 1, "jpg");
+
+        let raw_files =
+            get_filenames_of_folder_with_valid_extension(root, &["arw".to_string()], &[], &[])
+                .unwrap();
+        let jpg_files =
+            get_filenames_of_folder_with_valid_extension(root, &["jpg".to_string()], &[], &[])
+                .unwrap();
+
+        let (matched, unused) = partition_matched_files(raw_files, jpg_files);
+
+        // Shoot B's identically-named JPG must still be flagged even though
+        // shoot A's RAW of the same name survives; the conventional `JPG`
+        // leaf is stripped so both sides scope to the same shoot directory.
+        assert_eq!(matched.len(), 1);
+        assert_eq!(unused.len(), 1);
+        assert_eq!(unused[0].relative_dir(), Path::new("2024/portrait"));
+
+        tmp_dir.close().unwrap();
+    }
 }
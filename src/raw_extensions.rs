@@ -0,0 +1,6 @@
+/// RAW file extensions recognized out of the box, covering the major
+/// camera manufacturers so users aren't limited to Sony's `.arw`.
+pub const RAW_IMAGE_EXTENSIONS: &[&str] = &[
+    "arw", "cr2", "cr3", "nef", "nrw", "orf", "raf", "rw2", "dng", "pef", "srw", "3fr", "iiq",
+    "erf", "kdc", "mos", "raw", "rwl", "x3f",
+];
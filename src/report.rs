@@ -0,0 +1,58 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A single matched or unmatched file, recorded so the user has an
+/// auditable trail of what would be (or was) deleted and what survived.
+#[derive(Serialize)]
+pub struct ReportEntry {
+    pub name: String,
+    pub extension: String,
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+/// Summary of a cull run: how many files were scanned on each side and
+/// how many ended up flagged for deletion (JPGs or RAWs, depending on
+/// `--direction`).
+#[derive(Serialize)]
+pub struct Report {
+    pub raw_scanned: usize,
+    pub jpg_scanned: usize,
+    pub flagged: usize,
+    pub entries: Vec<ReportEntry>,
+}
+
+impl Report {
+    fn to_text(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("RAW files scanned: {}\n", self.raw_scanned));
+        out.push_str(&format!("JPG files scanned: {}\n", self.jpg_scanned));
+        out.push_str(&format!("Files flagged: {}\n\n", self.flagged));
+
+        for entry in &self.entries {
+            out.push_str(&format!(
+                "{} ({}): {:?} - {}\n",
+                entry.name, entry.extension, entry.path, entry.reason
+            ));
+        }
+
+        out
+    }
+
+    /// Writes the report to `path`, choosing the format from the file
+    /// extension: `.json` serializes the whole report, anything else
+    /// gets the plain-text summary.
+    pub fn write_to_file(&self, path: &Path) -> Result<()> {
+        let contents = if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+            serde_json::to_string_pretty(self)
+                .context("❌ Could not serialize report to JSON")?
+        } else {
+            self.to_text()
+        };
+
+        fs::write(path, contents)
+            .with_context(|| format!("❌ Could not write report to {:?}", path))
+    }
+}
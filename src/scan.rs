@@ -0,0 +1,130 @@
+use anyhow::{Context, Result};
+use glob::Pattern;
+use rayon::prelude::*;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+#[derive(Clone)]
+pub struct Filename {
+    pub filename: String,
+    pub name: String,
+    pub extension: String,
+    pub path: PathBuf,
+    /// Path relative to the scanned root, used to scope duplicate
+    /// detection to a single shoot folder and to match include/exclude
+    /// globs.
+    pub relative_path: PathBuf,
+}
+
+/// Conventional name of the JPG sibling folder, stripped when computing
+/// the shoot directory a file belongs to (see `Filename::relative_dir`).
+const JPG_FOLDER: &str = "JPG";
+
+impl Filename {
+    fn from_root(root: &Path, path: PathBuf) -> Self {
+        let filename = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("")
+            .to_string();
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("")
+            .to_string();
+        let extension = path
+            .extension()
+            .and_then(|s| s.to_str())
+            .unwrap_or("")
+            .to_string()
+            .to_lowercase();
+        let relative_path = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+
+        Self {
+            filename,
+            name,
+            extension,
+            path,
+            relative_path,
+        }
+    }
+
+    /// The shoot directory this file belongs to, relative to the scanned
+    /// root, so a JPG in one folder can't be "saved" by an identically
+    /// named RAW in a different one. A trailing `JPG` leaf is stripped so
+    /// a RAW at `2024/wedding/x.arw` and its sibling JPG at
+    /// `2024/wedding/JPG/x.jpg` are recognized as the same shoot even
+    /// when both are scanned from a shared root.
+    pub fn relative_dir(&self) -> PathBuf {
+        let dir = self.relative_path.parent().unwrap_or_else(|| Path::new(""));
+        let is_jpg_leaf = dir
+            .file_name()
+            .and_then(|leaf| leaf.to_str())
+            .is_some_and(|leaf| leaf.eq_ignore_ascii_case(JPG_FOLDER));
+
+        if is_jpg_leaf {
+            dir.parent().unwrap_or_else(|| Path::new("")).to_path_buf()
+        } else {
+            dir.to_path_buf()
+        }
+    }
+}
+
+/// Recursively walks `root`, collecting every file it contains. The walk
+/// itself is sequential (directory traversal doesn't parallelize well),
+/// but building a `Filename` for each entry runs across the rayon pool
+/// set up by `--threads`.
+pub fn get_filenames_of_folder(root: &Path) -> Result<Vec<Filename>> {
+    let paths = WalkDir::new(root)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .collect::<Vec<PathBuf>>();
+
+    Ok(paths
+        .into_par_iter()
+        .map(|path| Filename::from_root(root, path))
+        .collect())
+}
+
+fn compile_patterns(patterns: &[String]) -> Result<Vec<Pattern>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            Pattern::new(pattern)
+                .with_context(|| format!("❌ Invalid glob pattern {:?}", pattern))
+        })
+        .collect()
+}
+
+fn matches_any(path: &Path, patterns: &[Pattern]) -> bool {
+    let path = path.to_string_lossy();
+    patterns.iter().any(|pattern| pattern.matches(&path))
+}
+
+/// Recursively walks `root`, keeping only files with an allowed extension
+/// that also pass the `include`/`exclude` glob filters (matched against
+/// each file's path relative to `root`).
+pub fn get_filenames_of_folder_with_valid_extension(
+    root: &Path,
+    allowed_extensions: &[String],
+    include: &[String],
+    exclude: &[String],
+) -> Result<Vec<Filename>> {
+    let include_patterns = compile_patterns(include)?;
+    let exclude_patterns = compile_patterns(exclude)?;
+
+    get_filenames_of_folder(root).map(|files| {
+        files
+            .into_iter()
+            .filter(|file| {
+                allowed_extensions.iter().any(|ext| ext == &file.extension)
+            })
+            .filter(|file| {
+                include_patterns.is_empty() || matches_any(&file.relative_path, &include_patterns)
+            })
+            .filter(|file| !matches_any(&file.relative_path, &exclude_patterns))
+            .collect::<Vec<Filename>>()
+    })
+}